@@ -1,8 +1,93 @@
-use image_webp::WebPDecoder;
+use image_webp::{ColorType, WebPDecoder, WebPEncoder};
 use std::ffi::{CStr, CString};
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufRead, BufReader, Cursor, Seek};
 use std::os::raw::c_char;
+use std::slice;
+
+/// WebP校验失败的结构化错误
+///
+/// 相比裸字符串，枚举让调用方（尤其是跨FFI边界的C/C++）可以按错误类别分支处理，
+/// 而不必依赖对错误文本做子串匹配。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebpError {
+    /// 读取底层数据时发生IO错误（文件打开失败等）
+    Io,
+    /// RIFF/WEBP文件签名不合法
+    InvalidSignature,
+    /// 数据块头部无效或无法识别
+    ChunkHeaderInvalid,
+    /// 使用了当前不支持的WebP特性
+    UnsupportedFeature,
+    /// 数据在预期结束前被截断
+    Truncated,
+    /// 图片尺寸超出支持的范围
+    DimensionsTooLarge,
+}
+
+impl WebpError {
+    /// 返回稳定的数字错误码，供FFI调用方使用
+    pub fn code(self) -> u32 {
+        match self {
+            WebpError::Io => 1,
+            WebpError::InvalidSignature => 2,
+            WebpError::ChunkHeaderInvalid => 3,
+            WebpError::UnsupportedFeature => 4,
+            WebpError::Truncated => 5,
+            WebpError::DimensionsTooLarge => 6,
+        }
+    }
+}
+
+impl std::fmt::Display for WebpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            WebpError::Io => "failed to read webp data",
+            WebpError::InvalidSignature => "invalid webp signature",
+            WebpError::ChunkHeaderInvalid => "invalid or unrecognized chunk header",
+            WebpError::UnsupportedFeature => "unsupported webp feature",
+            WebpError::Truncated => "webp data is truncated",
+            WebpError::DimensionsTooLarge => "webp dimensions exceed supported limits",
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::error::Error for WebpError {}
+
+impl From<&image_webp::DecodingError> for WebpError {
+    fn from(err: &image_webp::DecodingError) -> Self {
+        use image_webp::DecodingError::*;
+        match err {
+            IoError(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => WebpError::Truncated,
+            IoError(_) => WebpError::Io,
+            RiffSignatureInvalid(_) | WebpSignatureInvalid(_) => WebpError::InvalidSignature,
+            ChunkHeaderInvalid(_) => WebpError::ChunkHeaderInvalid,
+            ImageTooLarge => WebpError::DimensionsTooLarge,
+            _ => WebpError::UnsupportedFeature,
+        }
+    }
+}
+
+/// WebP底层比特流的压缩格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebpFormat {
+    /// VP8有损压缩
+    Lossy,
+    /// VP8L无损压缩
+    Lossless,
+    /// VP8X扩展容器（携带动画、ICC/EXIF/XMP等额外特性）
+    Extended,
+}
+
+/// format位于bit 0-1，其余特性各占一个独立的bit
+const FEATURE_FLAG_FORMAT_MASK: u32 = 0b11;
+const FEATURE_FLAG_FORMAT_LOSSY: u32 = 0;
+const FEATURE_FLAG_FORMAT_LOSSLESS: u32 = 1;
+const FEATURE_FLAG_FORMAT_EXTENDED: u32 = 2;
+const FEATURE_FLAG_HAS_ICC_PROFILE: u32 = 1 << 2;
+const FEATURE_FLAG_HAS_EXIF_METADATA: u32 = 1 << 3;
+const FEATURE_FLAG_HAS_XMP_METADATA: u32 = 1 << 4;
 
 /// WebP图片信息结构体
 #[derive(Debug)]
@@ -12,29 +97,253 @@ pub struct WebpInfo {
     pub has_alpha: bool,
     pub is_animated: bool,
     pub num_frames: u32,
+    /// ANIM块中的循环次数，0表示无限循环
+    pub loop_count: u32,
+    /// ANIM块中的画布背景色，按`background_color_hint()`返回的RGBA字节顺序大端打包为一个u32
+    pub background_color: u32,
+    /// 所有帧的总播放时长（毫秒），只有`validate_webp_detailed`会填充此字段
+    pub total_duration_ms: u32,
+    /// 每一帧的播放时长（毫秒），只有`validate_webp_detailed`会填充此字段
+    pub frame_durations_ms: Vec<u32>,
+    /// 底层比特流是VP8（有损）、VP8L（无损）还是VP8X（扩展容器）
+    pub format: WebpFormat,
+    /// 是否携带ICC颜色配置文件
+    pub has_icc_profile: bool,
+    /// 是否携带EXIF元数据
+    pub has_exif_metadata: bool,
+    /// 是否携带XMP元数据
+    pub has_xmp_metadata: bool,
+}
+
+/// 将`image_webp::LoopCount`折算为u32，0表示无限循环
+fn loop_count_to_u32(loop_count: image_webp::LoopCount) -> u32 {
+    match loop_count {
+        image_webp::LoopCount::Forever => 0,
+        image_webp::LoopCount::Times(n) => n.get() as u32,
+    }
 }
 
 impl WebpInfo {
-    fn new_valid(decoder: &WebPDecoder<BufReader<File>>) -> Self {
-        WebpInfo {
+    fn new_valid<R: BufRead + Seek>(decoder: &mut WebPDecoder<R>) -> Result<Self, WebpError> {
+        let has_alpha = decoder.has_alpha();
+        let is_animated = decoder.is_animated();
+        let has_icc_profile = decoder
+            .icc_profile()
+            .map_err(|e| WebpError::from(&e))?
+            .is_some();
+        let has_exif_metadata = decoder
+            .exif_metadata()
+            .map_err(|e| WebpError::from(&e))?
+            .is_some();
+        let has_xmp_metadata = decoder
+            .xmp_metadata()
+            .map_err(|e| WebpError::from(&e))?
+            .is_some();
+        let is_lossy = decoder.is_lossy();
+
+        // VP8L（无损）位流自带alpha通道，不需要VP8X容器；只有有损（VP8）图片携带
+        // alpha时才依赖VP8X+ALPH，因此alpha只在与is_lossy同时成立时才提示Extended。
+        // 动画或ICC/EXIF/XMP元数据无论有损/无损都必须由VP8X容器承载。
+        let format = if is_animated
+            || (has_alpha && is_lossy)
+            || has_icc_profile
+            || has_exif_metadata
+            || has_xmp_metadata
+        {
+            WebpFormat::Extended
+        } else if is_lossy {
+            WebpFormat::Lossy
+        } else {
+            WebpFormat::Lossless
+        };
+
+        let background_color = decoder
+            .background_color_hint()
+            .map(u32::from_be_bytes)
+            .unwrap_or(0);
+
+        Ok(WebpInfo {
             width: decoder.dimensions().0,
             height: decoder.dimensions().1,
-            has_alpha: decoder.has_alpha(),
-            is_animated: decoder.is_animated(),
+            has_alpha,
+            is_animated,
             num_frames: decoder.num_frames(),
+            loop_count: loop_count_to_u32(decoder.loop_count()),
+            background_color,
+            total_duration_ms: 0,
+            frame_durations_ms: Vec::new(),
+            format,
+            has_icc_profile,
+            has_exif_metadata,
+            has_xmp_metadata,
+        })
+    }
+
+    /// 将format与ICC/EXIF/XMP标志打包为一个位掩码，供FFI调用方一次性获取
+    pub fn feature_flags(&self) -> u32 {
+        let format_bits = match self.format {
+            WebpFormat::Lossy => FEATURE_FLAG_FORMAT_LOSSY,
+            WebpFormat::Lossless => FEATURE_FLAG_FORMAT_LOSSLESS,
+            WebpFormat::Extended => FEATURE_FLAG_FORMAT_EXTENDED,
+        };
+
+        let mut flags = format_bits & FEATURE_FLAG_FORMAT_MASK;
+        if self.has_icc_profile {
+            flags |= FEATURE_FLAG_HAS_ICC_PROFILE;
+        }
+        if self.has_exif_metadata {
+            flags |= FEATURE_FLAG_HAS_EXIF_METADATA;
+        }
+        if self.has_xmp_metadata {
+            flags |= FEATURE_FLAG_HAS_XMP_METADATA;
         }
+        flags
     }
 }
 
 /// 校验WebP图片格式是否合法
-pub fn validate_webp(path: &str) -> Result<WebpInfo, String> {
-    let file = File::open(path).map_err(|e| format!("failed to open file: {}", e))?;
+pub fn validate_webp(path: &str) -> Result<WebpInfo, WebpError> {
+    let file = File::open(path).map_err(|_| WebpError::Io)?;
     let reader = BufReader::new(file);
 
     match WebPDecoder::new(reader) {
-        Ok(decoder) => Ok(WebpInfo::new_valid(&decoder)),
-        Err(e) => Err(format!("webp format validation failed: {:?}", e)),
+        Ok(mut decoder) => WebpInfo::new_valid(&mut decoder),
+        Err(e) => Err(WebpError::from(&e)),
+    }
+}
+
+/// 校验内存中的WebP字节数据是否合法
+///
+/// 与`validate_webp`相同，但直接操作字节切片，适用于已经持有数据的调用方
+/// （如HTTP上传处理、消息队列），避免先落盘再读取的开销。
+pub fn validate_webp_bytes(data: &[u8]) -> Result<WebpInfo, WebpError> {
+    let cursor = Cursor::new(data);
+
+    match WebPDecoder::new(cursor) {
+        Ok(mut decoder) => WebpInfo::new_valid(&mut decoder),
+        Err(e) => Err(WebpError::from(&e)),
+    }
+}
+
+/// 依次解码每一帧，收集每帧播放时长（毫秒）并返回总时长
+fn collect_frame_durations<R: BufRead + Seek>(
+    decoder: &mut WebPDecoder<R>,
+    num_frames: u32,
+) -> Result<(Vec<u32>, u32), WebpError> {
+    let buf_size = decoder
+        .output_buffer_size()
+        .ok_or(WebpError::DimensionsTooLarge)?;
+    let mut buf = vec![0u8; buf_size];
+    let mut durations = Vec::with_capacity(num_frames as usize);
+
+    for _ in 0..num_frames {
+        let duration = decoder
+            .read_frame(&mut buf)
+            .map_err(|e| WebpError::from(&e))?;
+        durations.push(duration);
+    }
+
+    let total_ms = durations.iter().sum();
+    Ok((durations, total_ms))
+}
+
+/// 校验WebP文件并附带完整的动画元数据（循环次数、总时长、每帧时长）
+///
+/// 与`validate_webp`相比，此函数会逐帧解码动画WebP以收集每一帧的播放时长，
+/// 因此比`validate_webp`更慢，适合播放器、缩略图生成器等需要精确时间信息的场景。
+pub fn validate_webp_detailed(path: &str) -> Result<WebpInfo, WebpError> {
+    let file = File::open(path).map_err(|_| WebpError::Io)?;
+    let reader = BufReader::new(file);
+
+    let mut decoder = WebPDecoder::new(reader).map_err(|e| WebpError::from(&e))?;
+    let mut info = WebpInfo::new_valid(&mut decoder)?;
+
+    if info.is_animated {
+        let (durations, total_ms) = collect_frame_durations(&mut decoder, info.num_frames)?;
+        info.frame_durations_ms = durations;
+        info.total_duration_ms = total_ms;
+    }
+
+    Ok(info)
+}
+
+/// 批量校验多个WebP文件
+///
+/// 内部将输入划分给一个大小固定的工作线程池（按`available_parallelism`确定线程数）
+/// 并行解码，结果按输入顺序返回。线程数不随批量大小增长，避免成千上万路径的
+/// 批次耗尽操作系统线程数/文件句柄限制，反而比串行逐个调用更慢、更不可靠。
+pub fn validate_webp_batch(paths: &[&str]) -> Vec<Result<WebpInfo, WebpError>> {
+    if paths.is_empty() {
+        return Vec::new();
+    }
+
+    let worker_count = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(paths.len());
+    let chunk_size = paths.len().div_ceil(worker_count);
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = paths
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|path| validate_webp(path))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+/// 从RGBA像素缓冲区编码生成WebP图片数据
+///
+/// `pixels`必须是`width * height * 4`字节的紧密排列RGBA缓冲区。`image_webp::WebPEncoder`
+/// 目前只实现了VP8L无损编码，没有有损路径，因此`lossless`必须为true，否则返回错误；
+/// `quality`与`effort`暂时没有对应的编码器旋钮可用，保留在签名中是为了未来换用支持
+/// 有损编码和可调压缩强度的后端时不必再改调用方接口。
+pub fn encode_webp_rgba(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    _quality: f32,
+    lossless: bool,
+    _effort: u8,
+) -> Result<Vec<u8>, String> {
+    let expected_len = width as usize * height as usize * 4;
+    if pixels.len() != expected_len {
+        return Err(format!(
+            "pixel buffer length {} does not match {}x{} RGBA ({} bytes expected)",
+            pixels.len(),
+            width,
+            height,
+            expected_len
+        ));
+    }
+
+    if !lossless {
+        return Err(
+            "lossy webp encoding is not supported: image_webp's WebPEncoder only implements \
+             VP8L lossless encoding"
+                .to_string(),
+        );
     }
+
+    let mut output = Vec::new();
+    let mut encoder = WebPEncoder::new(&mut output);
+
+    encoder
+        .encode(pixels, width, height, ColorType::Rgba8)
+        .map_err(|e| format!("webp encoding failed: {:?}", e))?;
+
+    Ok(output)
 }
 
 /// C兼容的WebP校验结果结构体
@@ -46,6 +355,13 @@ pub struct WebpValidationResult {
     pub has_alpha: bool,
     pub is_animated: bool,
     pub num_frames: u32,
+    pub loop_count: u32,
+    pub background_color: u32,
+    pub total_duration_ms: u32,
+    pub frame_durations: *mut u32,
+    pub frame_durations_len: usize,
+    pub feature_flags: u32,
+    pub error_code: u32,
     pub error_message: *mut c_char,
 }
 
@@ -65,6 +381,13 @@ pub unsafe extern "C" fn validate_webp_ffi(path: *const c_char) -> WebpValidatio
             has_alpha: false,
             is_animated: false,
             num_frames: 0,
+            loop_count: 0,
+            background_color: 0,
+            total_duration_ms: 0,
+            frame_durations: std::ptr::null_mut(),
+            frame_durations_len: 0,
+            feature_flags: 0,
+            error_code: WebpError::Io.code(),
             error_message: CString::new("path is null").unwrap().into_raw(),
         };
     }
@@ -80,6 +403,13 @@ pub unsafe extern "C" fn validate_webp_ffi(path: *const c_char) -> WebpValidatio
                 has_alpha: false,
                 is_animated: false,
                 num_frames: 0,
+                loop_count: 0,
+                background_color: 0,
+                total_duration_ms: 0,
+                frame_durations: std::ptr::null_mut(),
+                frame_durations_len: 0,
+                feature_flags: 0,
+                error_code: WebpError::Io.code(),
                 error_message: CString::new("invalid utf-8 in path").unwrap().into_raw(),
             };
         }
@@ -93,6 +423,13 @@ pub unsafe extern "C" fn validate_webp_ffi(path: *const c_char) -> WebpValidatio
             has_alpha: info.has_alpha,
             is_animated: info.is_animated,
             num_frames: info.num_frames,
+            loop_count: info.loop_count,
+            background_color: info.background_color,
+            total_duration_ms: info.total_duration_ms,
+            frame_durations: std::ptr::null_mut(),
+            frame_durations_len: 0,
+            feature_flags: info.feature_flags(),
+            error_code: 0,
             error_message: std::ptr::null_mut(),
         },
         Err(err) => WebpValidationResult {
@@ -102,7 +439,180 @@ pub unsafe extern "C" fn validate_webp_ffi(path: *const c_char) -> WebpValidatio
             has_alpha: false,
             is_animated: false,
             num_frames: 0,
-            error_message: CString::new(err).unwrap().into_raw(),
+            loop_count: 0,
+            background_color: 0,
+            total_duration_ms: 0,
+            frame_durations: std::ptr::null_mut(),
+            frame_durations_len: 0,
+            feature_flags: 0,
+            error_code: err.code(),
+            error_message: CString::new(err.to_string()).unwrap().into_raw(),
+        },
+    }
+}
+
+/// 通过FFI校验内存中的WebP字节数据
+///
+/// # Safety
+/// 调用方必须确保：
+/// 1. data指向至少len字节的有效内存
+/// 2. 使用free_error_message释放error_message
+#[no_mangle]
+pub unsafe extern "C" fn validate_webp_bytes_ffi(
+    data: *const u8,
+    len: usize,
+) -> WebpValidationResult {
+    if data.is_null() {
+        return WebpValidationResult {
+            is_valid: false,
+            width: 0,
+            height: 0,
+            has_alpha: false,
+            is_animated: false,
+            num_frames: 0,
+            loop_count: 0,
+            background_color: 0,
+            total_duration_ms: 0,
+            frame_durations: std::ptr::null_mut(),
+            frame_durations_len: 0,
+            feature_flags: 0,
+            error_code: WebpError::Io.code(),
+            error_message: CString::new("data is null").unwrap().into_raw(),
+        };
+    }
+
+    let slice = unsafe { slice::from_raw_parts(data, len) };
+
+    match validate_webp_bytes(slice) {
+        Ok(info) => WebpValidationResult {
+            is_valid: true,
+            width: info.width,
+            height: info.height,
+            has_alpha: info.has_alpha,
+            is_animated: info.is_animated,
+            num_frames: info.num_frames,
+            loop_count: info.loop_count,
+            background_color: info.background_color,
+            total_duration_ms: info.total_duration_ms,
+            frame_durations: std::ptr::null_mut(),
+            frame_durations_len: 0,
+            feature_flags: info.feature_flags(),
+            error_code: 0,
+            error_message: std::ptr::null_mut(),
+        },
+        Err(err) => WebpValidationResult {
+            is_valid: false,
+            width: 0,
+            height: 0,
+            has_alpha: false,
+            is_animated: false,
+            num_frames: 0,
+            loop_count: 0,
+            background_color: 0,
+            total_duration_ms: 0,
+            frame_durations: std::ptr::null_mut(),
+            frame_durations_len: 0,
+            feature_flags: 0,
+            error_code: err.code(),
+            error_message: CString::new(err.to_string()).unwrap().into_raw(),
+        },
+    }
+}
+
+/// 通过FFI校验WebP文件并附带完整的动画元数据（循环次数、总时长、每帧时长）
+///
+/// # Safety
+/// 调用方必须确保：
+/// 1. path是有效的以null结尾的C字符串
+/// 2. 使用free_error_message释放error_message，使用free_frame_durations释放frame_durations
+#[no_mangle]
+pub unsafe extern "C" fn validate_webp_detailed_ffi(path: *const c_char) -> WebpValidationResult {
+    if path.is_null() {
+        return WebpValidationResult {
+            is_valid: false,
+            width: 0,
+            height: 0,
+            has_alpha: false,
+            is_animated: false,
+            num_frames: 0,
+            loop_count: 0,
+            background_color: 0,
+            total_duration_ms: 0,
+            frame_durations: std::ptr::null_mut(),
+            frame_durations_len: 0,
+            feature_flags: 0,
+            error_code: WebpError::Io.code(),
+            error_message: CString::new("path is null").unwrap().into_raw(),
+        };
+    }
+
+    let c_str = unsafe { CStr::from_ptr(path) };
+    let path_str = match c_str.to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            return WebpValidationResult {
+                is_valid: false,
+                width: 0,
+                height: 0,
+                has_alpha: false,
+                is_animated: false,
+                num_frames: 0,
+                loop_count: 0,
+                background_color: 0,
+                total_duration_ms: 0,
+                frame_durations: std::ptr::null_mut(),
+                frame_durations_len: 0,
+                feature_flags: 0,
+                error_code: WebpError::Io.code(),
+                error_message: CString::new("invalid utf-8 in path").unwrap().into_raw(),
+            };
+        }
+    };
+
+    match validate_webp_detailed(path_str) {
+        Ok(info) => {
+            let feature_flags = info.feature_flags();
+            let mut durations = info.frame_durations_ms.into_boxed_slice();
+            let frame_durations_len = durations.len();
+            let frame_durations = if frame_durations_len == 0 {
+                std::ptr::null_mut()
+            } else {
+                durations.as_mut_ptr()
+            };
+            std::mem::forget(durations);
+
+            WebpValidationResult {
+                is_valid: true,
+                width: info.width,
+                height: info.height,
+                has_alpha: info.has_alpha,
+                is_animated: info.is_animated,
+                num_frames: info.num_frames,
+                loop_count: info.loop_count,
+                background_color: info.background_color,
+                total_duration_ms: info.total_duration_ms,
+                frame_durations,
+                frame_durations_len,
+                feature_flags,
+                error_code: 0,
+                error_message: std::ptr::null_mut(),
+            }
+        }
+        Err(err) => WebpValidationResult {
+            is_valid: false,
+            width: 0,
+            height: 0,
+            has_alpha: false,
+            is_animated: false,
+            num_frames: 0,
+            loop_count: 0,
+            background_color: 0,
+            total_duration_ms: 0,
+            frame_durations: std::ptr::null_mut(),
+            frame_durations_len: 0,
+            feature_flags: 0,
+            error_code: err.code(),
+            error_message: CString::new(err.to_string()).unwrap().into_raw(),
         },
     }
 }
@@ -122,6 +632,246 @@ pub unsafe extern "C" fn free_error_message(error_message: *mut c_char) {
     }
 }
 
+/// 释放validate_webp_detailed_ffi分配的每帧时长数组
+///
+/// # Safety
+/// 调用方必须确保：
+/// 1. frame_durations和len是由validate_webp_detailed_ffi返回的指针和长度
+/// 2. 每个指针只调用此函数一次
+#[no_mangle]
+pub unsafe extern "C" fn free_frame_durations(frame_durations: *mut u32, len: usize) {
+    if !frame_durations.is_null() {
+        unsafe {
+            let _ = Vec::from_raw_parts(frame_durations, len, len);
+        }
+    }
+}
+
+/// C兼容的WebP编码结果结构体
+#[repr(C)]
+pub struct WebpEncodeResult {
+    pub success: bool,
+    pub data: *mut u8,
+    pub len: usize,
+    pub error_message: *mut c_char,
+}
+
+/// 通过FFI将RGBA像素缓冲区编码为WebP
+///
+/// # Safety
+/// 调用方必须确保：
+/// 1. pixels指向至少`width * height * 4`字节的有效内存
+/// 2. 使用free_webp_encode_buffer释放data，使用free_error_message释放error_message
+#[no_mangle]
+pub unsafe extern "C" fn encode_webp_rgba_ffi(
+    pixels: *const u8,
+    width: u32,
+    height: u32,
+    quality: f32,
+    lossless: bool,
+    effort: u8,
+) -> WebpEncodeResult {
+    if pixels.is_null() {
+        return WebpEncodeResult {
+            success: false,
+            data: std::ptr::null_mut(),
+            len: 0,
+            error_message: CString::new("pixels is null").unwrap().into_raw(),
+        };
+    }
+
+    let expected_len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|n| n.checked_mul(4));
+    let expected_len = match expected_len {
+        Some(len) => len,
+        None => {
+            return WebpEncodeResult {
+                success: false,
+                data: std::ptr::null_mut(),
+                len: 0,
+                error_message: CString::new(format!(
+                    "{}x{} RGBA buffer size overflows usize",
+                    width, height
+                ))
+                .unwrap()
+                .into_raw(),
+            };
+        }
+    };
+    let slice = unsafe { slice::from_raw_parts(pixels, expected_len) };
+
+    match encode_webp_rgba(slice, width, height, quality, lossless, effort) {
+        Ok(data) => {
+            let mut data = data.into_boxed_slice();
+            let len = data.len();
+            let ptr = data.as_mut_ptr();
+            std::mem::forget(data);
+            WebpEncodeResult {
+                success: true,
+                data: ptr,
+                len,
+                error_message: std::ptr::null_mut(),
+            }
+        }
+        Err(err) => WebpEncodeResult {
+            success: false,
+            data: std::ptr::null_mut(),
+            len: 0,
+            error_message: CString::new(err).unwrap().into_raw(),
+        },
+    }
+}
+
+/// 释放encode_webp_rgba_ffi分配的输出缓冲区
+///
+/// # Safety
+/// 调用方必须确保：
+/// 1. data和len是由encode_webp_rgba_ffi返回的指针和长度
+/// 2. 每个指针只调用此函数一次
+#[no_mangle]
+pub unsafe extern "C" fn free_webp_encode_buffer(data: *mut u8, len: usize) {
+    if !data.is_null() {
+        unsafe {
+            let _ = Vec::from_raw_parts(data, len, len);
+        }
+    }
+}
+
+/// C兼容的批量校验结果结构体
+#[repr(C)]
+pub struct WebpBatchResult {
+    pub results: *mut WebpValidationResult,
+    pub len: usize,
+    pub total: usize,
+    pub valid_count: usize,
+    pub invalid_count: usize,
+}
+
+/// 通过FFI批量校验多个WebP文件
+///
+/// # Safety
+/// 调用方必须确保：
+/// 1. paths指向len个有效的、以null结尾的C字符串指针
+/// 2. 使用free_webp_batch_result释放返回的结果数组
+#[no_mangle]
+pub unsafe extern "C" fn validate_webp_batch_ffi(
+    paths: *const *const c_char,
+    len: usize,
+) -> WebpBatchResult {
+    if paths.is_null() {
+        return WebpBatchResult {
+            results: std::ptr::null_mut(),
+            len: 0,
+            total: 0,
+            valid_count: 0,
+            invalid_count: 0,
+        };
+    }
+
+    let ptrs = unsafe { slice::from_raw_parts(paths, len) };
+    let owned_paths: Vec<String> = ptrs
+        .iter()
+        .map(|&p| {
+            if p.is_null() {
+                String::new()
+            } else {
+                unsafe { CStr::from_ptr(p) }.to_string_lossy().into_owned()
+            }
+        })
+        .collect();
+    let path_refs: Vec<&str> = owned_paths.iter().map(|s| s.as_str()).collect();
+
+    let mut valid_count = 0usize;
+    let mut invalid_count = 0usize;
+
+    let mut results: Vec<WebpValidationResult> = validate_webp_batch(&path_refs)
+        .into_iter()
+        .map(|result| match result {
+            Ok(info) => {
+                valid_count += 1;
+                WebpValidationResult {
+                    is_valid: true,
+                    width: info.width,
+                    height: info.height,
+                    has_alpha: info.has_alpha,
+                    is_animated: info.is_animated,
+                    num_frames: info.num_frames,
+                    loop_count: info.loop_count,
+                    background_color: info.background_color,
+                    total_duration_ms: info.total_duration_ms,
+                    frame_durations: std::ptr::null_mut(),
+                    frame_durations_len: 0,
+                    feature_flags: info.feature_flags(),
+                    error_code: 0,
+                    error_message: std::ptr::null_mut(),
+                }
+            }
+            Err(err) => {
+                invalid_count += 1;
+                WebpValidationResult {
+                    is_valid: false,
+                    width: 0,
+                    height: 0,
+                    has_alpha: false,
+                    is_animated: false,
+                    num_frames: 0,
+                    loop_count: 0,
+                    background_color: 0,
+                    total_duration_ms: 0,
+                    frame_durations: std::ptr::null_mut(),
+                    frame_durations_len: 0,
+                    feature_flags: 0,
+                    error_code: err.code(),
+                    error_message: CString::new(err.to_string()).unwrap().into_raw(),
+                }
+            }
+        })
+        .collect();
+
+    let total = results.len();
+    let results_len = results.len();
+    let ptr = results.as_mut_ptr();
+    std::mem::forget(results);
+
+    WebpBatchResult {
+        results: ptr,
+        len: results_len,
+        total,
+        valid_count,
+        invalid_count,
+    }
+}
+
+/// 释放validate_webp_batch_ffi分配的结果数组，包括每个元素内部持有的内存
+///
+/// # Safety
+/// 调用方必须确保：
+/// 1. results和len是由validate_webp_batch_ffi返回的指针和长度
+/// 2. 每个指针只调用此函数一次
+#[no_mangle]
+pub unsafe extern "C" fn free_webp_batch_result(results: *mut WebpValidationResult, len: usize) {
+    if results.is_null() {
+        return;
+    }
+
+    unsafe {
+        let results_vec = Vec::from_raw_parts(results, len, len);
+        for result in results_vec {
+            if !result.error_message.is_null() {
+                let _ = CString::from_raw(result.error_message);
+            }
+            if !result.frame_durations.is_null() {
+                let _ = Vec::from_raw_parts(
+                    result.frame_durations,
+                    result.frame_durations_len,
+                    result.frame_durations_len,
+                );
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,15 +926,13 @@ mod tests {
         assert!(result.is_err(), "fake webp should fail validation");
 
         let error = result.unwrap_err();
-        assert!(
-            error.contains("webp format validation failed"),
-            "error should contain 'webp format validation failed'"
-        );
-        assert!(
-            error.contains("ChunkHeaderInvalid"),
-            "error should contain 'ChunkHeaderInvalid', actual: {}",
+        assert_eq!(
+            error,
+            WebpError::ChunkHeaderInvalid,
+            "error should be ChunkHeaderInvalid, actual: {:?}",
             error
         );
+        assert_eq!(error.code(), 3);
 
         println!("fake webp test passed:");
         println!("  error message: {}", error);
@@ -197,15 +945,199 @@ mod tests {
         assert!(result.is_err(), "nonexistent file should return error");
 
         let error = result.unwrap_err();
-        assert!(
-            error.contains("failed to open file"),
-            "error should contain 'failed to open file'"
-        );
+        assert_eq!(error, WebpError::Io, "error should be Io, actual: {:?}", error);
 
         println!("nonexistent file test passed:");
         println!("  error message: {}", error);
     }
 
+    #[test]
+    fn test_validate_webp_batch() {
+        let paths = [
+            "images/static.webp",
+            "images/dynamic.webp",
+            "images/fake.webp",
+        ];
+
+        let results = validate_webp_batch(&paths);
+
+        assert_eq!(results.len(), paths.len(), "results should preserve order and count");
+        assert!(results[0].is_ok(), "static webp should pass validation");
+        assert!(results[1].is_ok(), "dynamic webp should pass validation");
+        assert!(results[2].is_err(), "fake webp should fail validation");
+    }
+
+    #[test]
+    fn test_validate_webp_batch_large_preserves_order() {
+        let fixtures = ["images/static.webp", "images/dynamic.webp", "images/fake.webp"];
+        // 远大于典型的可用并行度，确保分片到固定数量线程池的批次仍按输入顺序返回
+        let paths: Vec<&str> = fixtures.iter().cycle().take(50).copied().collect();
+
+        let results = validate_webp_batch(&paths);
+
+        assert_eq!(results.len(), paths.len(), "results should preserve order and count");
+        for (i, result) in results.iter().enumerate() {
+            let expect_ok = i % fixtures.len() != 2;
+            assert_eq!(
+                result.is_ok(),
+                expect_ok,
+                "result at index {i} should match validate_webp({})",
+                paths[i]
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_webp_rgba_lossless_roundtrip() {
+        let width = 4u32;
+        let height = 4u32;
+        let pixels = vec![255u8; (width * height * 4) as usize];
+
+        let result = encode_webp_rgba(&pixels, width, height, 80.0, true, 6);
+        assert!(result.is_ok(), "lossless encode should succeed");
+
+        let data = result.unwrap();
+        assert!(!data.is_empty(), "encoded data should not be empty");
+
+        let info = validate_webp_bytes(&data).expect("encoded webp should validate");
+        assert_eq!(info.width, width);
+        assert_eq!(info.height, height);
+    }
+
+    #[test]
+    fn test_lossless_alpha_is_not_reported_as_extended() {
+        let width = 4u32;
+        let height = 4u32;
+        let mut pixels = vec![255u8; (width * height * 4) as usize];
+        // 把所有像素的alpha通道设为非255，确保encoder写入真实的透明信息
+        for px in pixels.chunks_mut(4) {
+            px[3] = 128;
+        }
+
+        let data = encode_webp_rgba(&pixels, width, height, 80.0, true, 6)
+            .expect("lossless encode should succeed");
+
+        let info = validate_webp_bytes(&data).expect("encoded webp should validate");
+        assert!(info.has_alpha, "encoded image should carry an alpha channel");
+        assert_eq!(
+            info.format,
+            WebpFormat::Lossless,
+            "VP8L lossless images carry alpha natively and must not be reported as Extended"
+        );
+    }
+
+    #[test]
+    fn test_encode_webp_rgba_lossy_unsupported() {
+        let width = 4u32;
+        let height = 4u32;
+        let pixels = vec![128u8; (width * height * 4) as usize];
+
+        let result = encode_webp_rgba(&pixels, width, height, 80.0, false, 0);
+        assert!(
+            result.is_err(),
+            "lossy encoding is not supported by image_webp's encoder and should fail"
+        );
+    }
+
+    #[test]
+    fn test_encode_webp_rgba_invalid_buffer() {
+        let result = encode_webp_rgba(&[0u8; 3], 4, 4, 80.0, false, 6);
+        assert!(result.is_err(), "mismatched buffer length should fail");
+    }
+
+    #[test]
+    fn test_webp_format_and_feature_flags() {
+        let result = validate_webp("images/static.webp");
+        assert!(result.is_ok());
+
+        let info = result.unwrap();
+        assert!(
+            matches!(
+                info.format,
+                WebpFormat::Lossy | WebpFormat::Lossless | WebpFormat::Extended
+            ),
+            "format should be one of the known variants"
+        );
+
+        let flags = info.feature_flags();
+        assert_eq!(
+            flags & FEATURE_FLAG_FORMAT_MASK,
+            match info.format {
+                WebpFormat::Lossy => FEATURE_FLAG_FORMAT_LOSSY,
+                WebpFormat::Lossless => FEATURE_FLAG_FORMAT_LOSSLESS,
+                WebpFormat::Extended => FEATURE_FLAG_FORMAT_EXTENDED,
+            },
+            "low bits of feature_flags should encode the format"
+        );
+
+        println!("format and feature flags test passed:");
+        println!("  format: {:?}", info.format);
+        println!("  feature flags: {:#b}", flags);
+    }
+
+    #[test]
+    fn test_validate_webp_detailed_animated() {
+        let result = validate_webp_detailed("images/dynamic.webp");
+
+        assert!(result.is_ok(), "dynamic webp should pass detailed validation");
+
+        let info = result.unwrap();
+        assert_eq!(
+            info.frame_durations_ms.len(),
+            info.num_frames as usize,
+            "should have one duration per frame"
+        );
+        assert_eq!(
+            info.total_duration_ms,
+            info.frame_durations_ms.iter().sum::<u32>(),
+            "total duration should be the sum of per-frame durations"
+        );
+
+        println!("detailed animated webp test passed:");
+        println!("  loop count: {}", info.loop_count);
+        println!("  total duration: {}ms", info.total_duration_ms);
+        println!("  frame durations: {:?}", info.frame_durations_ms);
+    }
+
+    #[test]
+    fn test_validate_webp_detailed_static() {
+        let result = validate_webp_detailed("images/static.webp");
+
+        assert!(result.is_ok(), "static webp should pass detailed validation");
+
+        let info = result.unwrap();
+        assert!(
+            info.frame_durations_ms.is_empty(),
+            "static image should have no per-frame durations"
+        );
+        assert_eq!(info.total_duration_ms, 0);
+    }
+
+    #[test]
+    fn test_validate_webp_bytes() {
+        let data = std::fs::read("images/static.webp").expect("failed to read fixture");
+        let result = validate_webp_bytes(&data);
+
+        assert!(result.is_ok(), "static webp bytes should pass validation");
+
+        let info = result.unwrap();
+        assert!(
+            info.width > 0 && info.height > 0,
+            "should have valid dimensions"
+        );
+
+        println!("validate_webp_bytes test passed:");
+        println!("  dimensions: {}x{}", info.width, info.height);
+    }
+
+    #[test]
+    fn test_validate_webp_bytes_fake() {
+        let data = std::fs::read("images/fake.webp").expect("failed to read fixture");
+        let result = validate_webp_bytes(&data);
+
+        assert!(result.is_err(), "fake webp bytes should fail validation");
+    }
+
     #[test]
     fn test_webp_info_debug() {
         let result = validate_webp("images/static.webp");